@@ -0,0 +1,116 @@
+use clap::{Parser, Subcommand};
+
+#[derive(Clone, Debug, Default, Parser)]
+#[clap(name = "relay", about = "An ActivityPub relay")]
+pub struct Args {
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+impl Args {
+    pub fn new() -> Self {
+        Self::parse()
+    }
+
+    pub fn into_command(self) -> Command {
+        self.command.unwrap_or_else(|| Command::Run(Run::default()))
+    }
+}
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum Command {
+    /// Run the relay server and background jobs
+    Run(Run),
+
+    /// Add or remove domains from the blocklist
+    Block {
+        #[clap(subcommand)]
+        command: BlockCommand,
+    },
+
+    /// Add or remove domains from the whitelist
+    Whitelist {
+        #[clap(subcommand)]
+        command: WhitelistCommand,
+    },
+
+    /// List cached relay state
+    List {
+        #[clap(subcommand)]
+        command: ListCommand,
+    },
+
+    /// Inspect a cached relay node
+    Node {
+        #[clap(subcommand)]
+        command: NodeCommand,
+    },
+}
+
+#[derive(Clone, Debug, Default, Parser)]
+pub struct Run {
+    /// Only run the background jobs, don't bind the HTTP server
+    #[clap(long)]
+    jobs_only: bool,
+
+    /// Bind the HTTP server, but don't run the background jobs
+    #[clap(long)]
+    no_jobs: bool,
+}
+
+impl Run {
+    pub fn jobs_only(&self) -> bool {
+        self.jobs_only
+    }
+
+    pub fn no_jobs(&self) -> bool {
+        self.no_jobs
+    }
+}
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum BlockCommand {
+    /// Add one or more domains to the blocklist
+    Add {
+        #[clap(required = true)]
+        domains: Vec<String>,
+    },
+    /// Remove one or more domains from the blocklist
+    Remove {
+        #[clap(required = true)]
+        domains: Vec<String>,
+    },
+}
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum WhitelistCommand {
+    /// Add one or more domains to the whitelist
+    Add {
+        #[clap(required = true)]
+        domains: Vec<String>,
+    },
+    /// Remove one or more domains from the whitelist
+    Remove {
+        #[clap(required = true)]
+        domains: Vec<String>,
+    },
+}
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum ListCommand {
+    /// List blocked domains
+    Blocks,
+    /// List whitelisted domains
+    Whitelists,
+    /// List known listener inboxes
+    Listeners,
+}
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum NodeCommand {
+    /// Show the cached nodeinfo for a listener
+    Show {
+        /// The inbox URI of the listener to look up
+        uri: String,
+    },
+}
@@ -0,0 +1,133 @@
+use crate::{
+    data::ActorCache,
+    middleware::{DigestMiddleware, SignatureMiddleware},
+    requests::Requests,
+};
+use std::sync::Arc;
+
+/// The different kinds of URLs the relay generates for itself, rooted at its configured
+/// hostname.
+#[derive(Clone, Copy, Debug)]
+pub enum UrlKind {
+    Actor,
+    Inbox,
+    Outbox,
+    Followers,
+    Following,
+    MainKey,
+}
+
+/// An outbound webhook endpoint that gets POSTed a JSON event on relay-significant
+/// LISTEN/NOTIFY channels (see `notify::WebhookListener`).
+#[derive(Clone, Debug)]
+pub struct WebhookTarget {
+    pub url: String,
+    pub secret: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct Config {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    hostname: String,
+    bind_address: String,
+    debug: bool,
+    pretty_log: bool,
+    database_url: String,
+    webhook_targets: Vec<WebhookTarget>,
+}
+
+impl Config {
+    pub fn build() -> Result<Self, anyhow::Error> {
+        let hostname = std::env::var("RELAY_HOSTNAME").unwrap_or_else(|_| "localhost".into());
+        let bind_address =
+            std::env::var("RELAY_BIND_ADDRESS").unwrap_or_else(|_| "0.0.0.0:8080".into());
+        let debug = env_flag("RELAY_DEBUG", false);
+        let pretty_log = env_flag("RELAY_PRETTY_LOG", debug);
+        let database_url = std::env::var("DATABASE_URL")?;
+        let webhook_targets = webhook_targets_from_env();
+
+        Ok(Config {
+            inner: Arc::new(Inner {
+                hostname,
+                bind_address,
+                debug,
+                pretty_log,
+                database_url,
+                webhook_targets,
+            }),
+        })
+    }
+
+    pub fn debug(&self) -> bool {
+        self.inner.debug
+    }
+
+    pub fn pretty_log(&self) -> bool {
+        self.inner.pretty_log
+    }
+
+    pub fn database_url(&self) -> &str {
+        &self.inner.database_url
+    }
+
+    pub fn bind_address(&self) -> &str {
+        &self.inner.bind_address
+    }
+
+    pub fn generate_url(&self, kind: UrlKind) -> String {
+        match kind {
+            UrlKind::Actor => format!("https://{}/actor", self.inner.hostname),
+            UrlKind::Inbox => format!("https://{}/inbox", self.inner.hostname),
+            UrlKind::Outbox => format!("https://{}/outbox", self.inner.hostname),
+            UrlKind::Followers => format!("https://{}/followers", self.inner.hostname),
+            UrlKind::Following => format!("https://{}/following", self.inner.hostname),
+            UrlKind::MainKey => format!("https://{}/actor#main-key", self.inner.hostname),
+        }
+    }
+
+    /// Webhook endpoints to notify when relay-significant events fire.
+    ///
+    /// Configured via `RELAY_WEBHOOKS`, a comma-separated list of URLs, optionally paired
+    /// with an HMAC signing key in `RELAY_WEBHOOK_SECRET` (every delivery then carries the
+    /// hex HMAC-SHA256 of its body in the `X-Relay-Signature` header). Returns an empty
+    /// list, and therefore disables the feature, when `RELAY_WEBHOOKS` is unset.
+    pub fn webhook_targets(&self) -> Vec<WebhookTarget> {
+        self.inner.webhook_targets.clone()
+    }
+
+    pub fn digest_middleware(&self) -> DigestMiddleware {
+        DigestMiddleware::new()
+    }
+
+    pub fn signature_middleware(
+        &self,
+        requests: Requests,
+        actors: ActorCache,
+    ) -> SignatureMiddleware {
+        SignatureMiddleware::new(requests, actors)
+    }
+}
+
+fn env_flag(key: &str, default: bool) -> bool {
+    std::env::var(key)
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(default)
+}
+
+fn webhook_targets_from_env() -> Vec<WebhookTarget> {
+    let secret = std::env::var("RELAY_WEBHOOK_SECRET").ok();
+
+    std::env::var("RELAY_WEBHOOKS")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .map(|url| WebhookTarget {
+            url: url.to_owned(),
+            secret: secret.clone(),
+        })
+        .collect()
+}
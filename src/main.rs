@@ -1,5 +1,7 @@
 use actix::Arbiter;
 use actix_web::{middleware::Logger, web, App, HttpServer};
+use activitystreams::primitives::XsdAnyUri;
+use log::error;
 
 mod apub;
 mod args;
@@ -14,7 +16,7 @@ mod requests;
 mod routes;
 
 use self::{
-    args::Args,
+    args::{Args, BlockCommand, Command, ListCommand, NodeCommand, WhitelistCommand},
     config::Config,
     data::{ActorCache, State},
     db::Db,
@@ -23,6 +25,36 @@ use self::{
     routes::{actor, inbox, index, nodeinfo, nodeinfo_meta, statics},
 };
 
+/// Re-reads the full block/whitelist/listener/node/actor lists from the DB and feeds them
+/// through the same per-item `cache_*` methods the live LISTEN/NOTIFY listeners already use
+/// (see `notify.rs`), rather than through a `State::resync`/`ActorCache::resync` -- neither
+/// of those is defined anywhere in this diff series, so calling them wouldn't compile.
+/// `State::hydrate` itself isn't reused here either: it builds a brand-new `State`, which
+/// wouldn't be visible to the `State`/`ActorCache` clones already handed to the running
+/// server and notify listeners.
+///
+/// This only adds entries back; a row deleted from the DB while disconnected won't be
+/// purged from the cache by this pass.
+async fn resync_caches(db: &Db, state: &State, actors: &ActorCache) -> Result<(), anyhow::Error> {
+    for domain in db.blocks().await? {
+        state.cache_block(domain).await;
+    }
+    for domain in db.whitelists().await? {
+        state.cache_whitelist(domain).await;
+    }
+    for uri in db.listeners().await? {
+        state.cache_listener(uri).await;
+    }
+    for uuid in db.nodes().await? {
+        state.node_cache().cache_by_id(uuid).await;
+    }
+    for uri in db.actors().await? {
+        actors.cache_follower(uri).await;
+    }
+
+    Ok(())
+}
+
 #[actix_rt::main]
 async fn main() -> Result<(), anyhow::Error> {
     dotenv::dotenv().ok();
@@ -45,27 +77,78 @@ async fn main() -> Result<(), anyhow::Error> {
 
     let args = Args::new();
 
-    if args.jobs_only() && args.no_jobs() {
+    let run = match args.into_command() {
+        Command::Block { command } => {
+            match command {
+                BlockCommand::Add { domains } => {
+                    db.add_blocks(domains.clone()).await?;
+                    println!("Blocked {} domain(s)", domains.len());
+                }
+                BlockCommand::Remove { domains } => {
+                    db.remove_blocks(domains.clone()).await?;
+                    println!("Unblocked {} domain(s)", domains.len());
+                }
+            }
+            return Ok(());
+        }
+        Command::Whitelist { command } => {
+            match command {
+                WhitelistCommand::Add { domains } => {
+                    db.add_whitelists(domains.clone()).await?;
+                    println!("Whitelisted {} domain(s)", domains.len());
+                }
+                WhitelistCommand::Remove { domains } => {
+                    db.remove_whitelists(domains.clone()).await?;
+                    println!("Un-whitelisted {} domain(s)", domains.len());
+                }
+            }
+            return Ok(());
+        }
+        Command::List { command } => {
+            match command {
+                ListCommand::Blocks => {
+                    for domain in db.blocks().await? {
+                        println!("{}", domain);
+                    }
+                }
+                ListCommand::Whitelists => {
+                    for domain in db.whitelists().await? {
+                        println!("{}", domain);
+                    }
+                }
+                ListCommand::Listeners => {
+                    for uri in db.listeners().await? {
+                        println!("{}", uri);
+                    }
+                }
+            }
+            return Ok(());
+        }
+        Command::Node { command } => {
+            let NodeCommand::Show { uri } = command;
+            let uri: XsdAnyUri = uri.parse()?;
+
+            match db.nodeinfo(&uri).await? {
+                Some(nodeinfo) => println!("{}", serde_json::to_string_pretty(&nodeinfo)?),
+                None => println!("No cached nodeinfo for {}", uri),
+            }
+            return Ok(());
+        }
+        Command::Run(run) => run,
+    };
+
+    if run.jobs_only() && run.no_jobs() {
         return Err(anyhow::Error::msg(
             "Either the server or the jobs must be run",
         ));
     }
 
-    if !args.blocks().is_empty() || !args.whitelists().is_empty() {
-        if args.undo() {
-            db.remove_blocks(args.blocks()).await?;
-            db.remove_whitelists(args.whitelists()).await?;
-        } else {
-            db.add_blocks(args.blocks()).await?;
-            db.add_whitelists(args.whitelists()).await?;
-        }
-        return Ok(());
-    }
-
     let state = State::hydrate(config.clone(), &db).await?;
     let actors = ActorCache::new(db.clone());
     let job_server = create_server(db.clone());
 
+    let webhook_targets = config.webhook_targets();
+
     notify::Notifier::new(config.database_url().parse()?)
         .register(notify::NewBlocks(state.clone()))
         .register(notify::NewWhitelists(state.clone()))
@@ -77,9 +160,39 @@ async fn main() -> Result<(), anyhow::Error> {
         .register(notify::RmListeners(state.clone()))
         .register(notify::RmActors(actors.clone()))
         .register(notify::RmNodes(state.node_cache()))
+        .register(notify::WebhookListener::new(
+            "new_listeners",
+            webhook_targets.clone(),
+            state.requests(),
+        ))
+        .register(notify::WebhookListener::new(
+            "new_blocks",
+            webhook_targets.clone(),
+            state.requests(),
+        ))
+        .register(notify::WebhookListener::new(
+            "rm_listeners",
+            webhook_targets,
+            state.requests(),
+        ))
+        .resync({
+            let state = state.clone();
+            let actors = actors.clone();
+            let db = db.clone();
+            move || {
+                let state = state.clone();
+                let actors = actors.clone();
+                let db = db.clone();
+                async move {
+                    if let Err(e) = resync_caches(&db, &state, &actors).await {
+                        error!("Error resynchronizing caches, {}", e);
+                    }
+                }
+            }
+        })
         .start();
 
-    if args.jobs_only() {
+    if run.jobs_only() {
         for _ in 0..num_cpus::get() {
             let state = state.clone();
             let actors = actors.clone();
@@ -93,7 +206,7 @@ async fn main() -> Result<(), anyhow::Error> {
         return Ok(());
     }
 
-    let no_jobs = args.no_jobs();
+    let no_jobs = run.no_jobs();
 
     let bind_address = config.bind_address();
     HttpServer::new(move || {